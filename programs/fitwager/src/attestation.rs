@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::solana_program::{ed25519_program, keccak};
+
+use crate::constants::MAX_ALLOWLIST_DEPTH;
+use crate::errors::FitError;
+
+// Layout of a single-signature Ed25519 program instruction:
+// [num_signatures: u8, padding: u8, offsets: 7 x u16, ...payload]
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Check that the instruction before the current one is an Ed25519 signature
+/// verification of `message` by one of the configured oracles. Returns the oracle key.
+pub fn verify_oracle_signature(
+    instructions_sysvar: &AccountInfo,
+    oracles: &[Pubkey],
+    message: &[u8],
+) -> Result<Pubkey> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, FitError::MissingAttestation);
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, FitError::MissingAttestation);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        FitError::InvalidAttestation
+    );
+
+    let read_u16 = |at: usize| -> usize {
+        let start = ED25519_OFFSETS_START + at * 2;
+        u16::from_le_bytes([data[start], data[start + 1]]) as usize
+    };
+    let public_key_offset = read_u16(2);
+    let message_offset = read_u16(4);
+    let message_size = read_u16(5);
+
+    // Signature, key and message must all live inside the Ed25519 instruction itself
+    for index_field in [1, 3, 6] {
+        require!(read_u16(index_field) == u16::MAX as usize, FitError::InvalidAttestation);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or_else(|| error!(FitError::InvalidAttestation))?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or_else(|| error!(FitError::InvalidAttestation))?;
+
+    let oracle = Pubkey::try_from(public_key).map_err(|_| error!(FitError::InvalidAttestation))?;
+    require!(oracles.contains(&oracle), FitError::UnauthorizedOracle);
+    require!(signed_message == message, FitError::InvalidAttestation);
+
+    Ok(oracle)
+}
+
+/// Check that `player` is a leaf of the allowlist Merkle tree with the given root.
+/// Leaves are `keccak(player)`; each parent is `keccak` of its two children in sorted order,
+/// so proofs carry no left/right flags.
+pub fn verify_allowlist_proof(root: &[u8; 32], player: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+    require!(proof.len() <= MAX_ALLOWLIST_DEPTH, FitError::NotInvited);
+
+    let mut node = keccak::hash(player.as_ref()).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    require!(node == *root, FitError::NotInvited);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar::instructions::{
+        construct_instructions_data, store_current_index, BorrowedInstruction,
+    };
+
+    fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        keccak::hashv(&[lo, hi]).to_bytes()
+    }
+
+    fn leaf(player: &Pubkey) -> [u8; 32] {
+        keccak::hash(player.as_ref()).to_bytes()
+    }
+
+    #[test]
+    fn allowlist_accepts_every_member() {
+        let players: Vec<Pubkey> = (1..=4).map(|id| Pubkey::new_from_array([id; 32])).collect();
+        let leaves: Vec<[u8; 32]> = players.iter().map(leaf).collect();
+        let (left, right) = (parent(&leaves[0], &leaves[1]), parent(&leaves[2], &leaves[3]));
+        let root = parent(&left, &right);
+
+        assert!(verify_allowlist_proof(&root, &players[0], &[leaves[1], right]).is_ok());
+        assert!(verify_allowlist_proof(&root, &players[3], &[leaves[2], left]).is_ok());
+        assert_eq!(
+            verify_allowlist_proof(&root, &players[0], &[leaves[2], right]).unwrap_err(),
+            FitError::NotInvited.into()
+        );
+        let outsider = Pubkey::new_from_array([9; 32]);
+        assert!(verify_allowlist_proof(&root, &outsider, &[leaves[1], right]).is_err());
+    }
+
+    #[test]
+    fn allowlist_rejects_proofs_past_max_depth() {
+        let player = Pubkey::new_from_array([1; 32]);
+        let proof = vec![[7; 32]; MAX_ALLOWLIST_DEPTH + 1];
+        let root = proof.iter().fold(leaf(&player), |node, sibling| parent(&node, sibling));
+
+        assert_eq!(
+            verify_allowlist_proof(&root, &player, &proof).unwrap_err(),
+            FitError::NotInvited.into()
+        );
+    }
+
+    /// Single-signature Ed25519 instruction data with the key, a dummy signature and the
+    /// message inline, and `ix_index` in all three instruction index fields
+    fn ed25519_data(oracle: &Pubkey, message: &[u8], ix_index: u16) -> Vec<u8> {
+        let header = (ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE) as u16;
+        let offsets = [header + 32, ix_index, header, ix_index, header + 96];
+        let mut data = vec![1, 0];
+        for field in offsets.into_iter().chain([message.len() as u16, ix_index]) {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(oracle.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    /// Run `verify_oracle_signature` as the instruction right after `ed25519_data`
+    fn verify(ed25519_data: &[u8], oracles: &[Pubkey], message: &[u8]) -> Result<Pubkey> {
+        let instructions = [
+            BorrowedInstruction {
+                program_id: &ed25519_program::ID,
+                accounts: vec![],
+                data: ed25519_data,
+            },
+            BorrowedInstruction { program_id: &crate::ID, accounts: vec![], data: &[] },
+        ];
+        let mut data = construct_instructions_data(&instructions);
+        store_current_index(&mut data, 1);
+
+        let key = anchor_lang::solana_program::sysvar::instructions::ID;
+        let owner = anchor_lang::solana_program::sysvar::ID;
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        verify_oracle_signature(&info, oracles, message)
+    }
+
+    #[test]
+    fn oracle_signature_inside_ed25519_instruction_is_accepted() {
+        let oracle = Pubkey::new_from_array([5; 32]);
+        let data = ed25519_data(&oracle, b"attestation", u16::MAX);
+
+        assert_eq!(verify(&data, &[oracle], b"attestation").unwrap(), oracle);
+        assert_eq!(
+            verify(&data, &[Pubkey::default()], b"attestation").unwrap_err(),
+            FitError::UnauthorizedOracle.into()
+        );
+        assert_eq!(
+            verify(&data, &[oracle], b"other message").unwrap_err(),
+            FitError::InvalidAttestation.into()
+        );
+    }
+
+    #[test]
+    fn oracle_signature_pointing_at_another_instruction_is_rejected() {
+        let oracle = Pubkey::new_from_array([5; 32]);
+        for ix_index in [0, 1] {
+            let data = ed25519_data(&oracle, b"attestation", ix_index);
+            assert_eq!(
+                verify(&data, &[oracle], b"attestation").unwrap_err(),
+                FitError::InvalidAttestation.into()
+            );
+        }
+    }
+}
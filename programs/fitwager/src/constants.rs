@@ -0,0 +1,20 @@
+// Basis point denominator for fee math (10,000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// Upper bound on the platform fee the admin can configure: 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+// Maximum number of authorized oracle keys stored in config
+pub const MAX_ORACLES: usize = 5;
+// Maximum number of paid places in a challenge's payout schedule
+pub const MAX_PAYOUT_PLACES: usize = 5;
+// Maximum number of SPL / Token-2022 mints accepted for entry fees
+pub const MAX_ACCEPTED_MINTS: usize = 8;
+// Longest late-sync window a creator can allow for score submissions: 3 days
+pub const MAX_SUBMISSION_GRACE_SECONDS: i64 = 3 * 24 * 60 * 60;
+// Seat limit of every challenge: finalize_challenge takes all participant accounts in one
+// legacy transaction, whose 1232-byte size limit fits about 28 of them next to the fixed
+// accounts, one signature and a compute budget instruction
+pub const MAX_PARTICIPANTS: u32 = 25;
+// Maximum number of players queued on a challenge's waitlist
+pub const MAX_WAITLIST: usize = 16;
+// Maximum Merkle proof length for private challenge allowlists (2^16 players)
+pub const MAX_ALLOWLIST_DEPTH: usize = 16;
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum FitError {
+    #[msg("Entry fee must be greater than 0")]
+    InvalidEntryFee,
+
+    #[msg("Entry fee is below the configured minimum")]
+    EntryFeeTooSmall,
+
+    #[msg("Duration must be greater than 0")]
+    InvalidDuration,
+
+    #[msg("Goal must be greater than 0")]
+    InvalidGoal,
+
+    #[msg("Challenge is closed")]
+    ChallengeClosed,
+
+    #[msg("Challenge has not ended yet")]
+    ChallengeNotOver,
+
+    #[msg("Challenge has ended")]
+    ChallengeEnded,
+
+    #[msg("Player has not joined this challenge")]
+    NotJoined,
+
+    #[msg("Player has already joined this challenge")]
+    AlreadyJoined,
+
+    #[msg("Wrong payment type for this challenge")]
+    WrongPaymentType,
+
+    #[msg("Only the creator can perform this action")]
+    NotCreator,
+
+    #[msg("Cannot cancel challenge with participants")]
+    HasParticipants,
+
+    #[msg("Invalid verification data")]
+    InvalidVerification,
+
+    #[msg("Participant account does not belong to this challenge")]
+    InvalidParticipant,
+
+    #[msg("Every joined participant must be provided for settlement")]
+    MissingParticipants,
+
+    #[msg("Participant account provided more than once")]
+    DuplicateParticipant,
+
+    #[msg("No participant has submitted a score")]
+    NoSubmissions,
+
+    #[msg("Only the config admin can perform this action")]
+    NotAdmin,
+
+    #[msg("Platform fee exceeds the maximum allowed")]
+    InvalidFee,
+
+    #[msg("Too many oracle keys")]
+    TooManyOracles,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Fee recipient is not the configured treasury")]
+    WrongTreasury,
+
+    #[msg("Missing Ed25519 oracle signature instruction")]
+    MissingAttestation,
+
+    #[msg("Malformed or mismatched score attestation")]
+    InvalidAttestation,
+
+    #[msg("Attestation is not signed by an authorized oracle")]
+    UnauthorizedOracle,
+
+    #[msg("Score attestation has expired")]
+    AttestationExpired,
+
+    #[msg("Attestation window is outside the challenge period")]
+    InvalidAttestationWindow,
+
+    #[msg("Challenge is not refundable")]
+    NotRefundable,
+
+    #[msg("Entry fee has already been refunded")]
+    AlreadyRefunded,
+
+    #[msg("Token account is not owned by the expected wallet")]
+    WrongTokenOwner,
+
+    #[msg("Payout schedule must have 1-5 non-zero places summing to 10000 bps")]
+    InvalidPayoutSchedule,
+
+    #[msg("Challenge has not been finalized")]
+    ChallengeNotFinalized,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Winnings have already been claimed")]
+    AlreadyClaimed,
+
+    #[msg("Token account mint does not match the challenge mint")]
+    WrongMint,
+
+    #[msg("Token challenges require the mint and escrow token accounts")]
+    MissingEscrowAccounts,
+
+    #[msg("Mint is not accepted for entry fees")]
+    MintNotAccepted,
+
+    #[msg("Too many accepted mints")]
+    TooManyMints,
+
+    #[msg("Participant's payout or refund has not been settled")]
+    ParticipantNotSettled,
+
+    #[msg("All participant accounts must be closed first")]
+    ParticipantsStillOpen,
+
+    #[msg("Escrow still holds funds")]
+    EscrowNotEmpty,
+
+    #[msg("Only the creator can settle before the grace period ends")]
+    SettlementGracePeriod,
+
+    #[msg("Only the account that finalized the challenge can claim the crank reward")]
+    NotCranker,
+
+    #[msg("Settlement deadline has passed; participants can claim refunds")]
+    SettlementExpired,
+
+    #[msg("Score submission deadline has passed")]
+    SubmissionsClosed,
+
+    #[msg("Start time must not be in the past and the join deadline must fall before the end")]
+    InvalidSchedule,
+
+    #[msg("Challenge has not started yet")]
+    ChallengeNotStarted,
+
+    #[msg("Join deadline has passed")]
+    JoinClosed,
+
+    #[msg("Challenge is full")]
+    ChallengeFull,
+
+    #[msg("Waitlist is full")]
+    WaitlistFull,
+
+    #[msg("Participant is on the waitlist")]
+    Waitlisted,
+
+    #[msg("Participant cap must be non-zero and no more than MAX_PARTICIPANTS")]
+    InvalidParticipantCap,
+
+    #[msg("Waitlist account is missing")]
+    MissingWaitlist,
+
+    #[msg("Minimum participants must be at least 2 and no more than the cap")]
+    InvalidMinParticipants,
+
+    #[msg("Leave penalty cannot exceed 100%")]
+    InvalidPenalty,
+
+    #[msg("This challenge does not allow leaving after the start")]
+    LeaveNotAllowed,
+
+    #[msg("Private challenges need an allowlist root; public challenges cannot have one")]
+    InvalidAllowlist,
+
+    #[msg("Player is not on this challenge's allowlist")]
+    NotInvited,
+
+    #[msg("Escrow account does not belong to this challenge")]
+    InvalidEscrow,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Escrow holds less than the challenge's tracked balance")]
+    EscrowMismatch,
+
+    #[msg("Attestation nonce was already used or is below the last accepted one")]
+    AttestationReplayed,
+
+    #[msg("Creators can only cancel before the start or while nobody has joined")]
+    CancelNotAllowed,
+
+    #[msg("Mint must be writable to harvest withheld transfer fees before closing the escrow")]
+    MintNotWritable,
+
+    #[msg("SOL challenges take no mint or escrow token account")]
+    UnexpectedTokenAccounts,
+}
@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_2022_extensions::transfer_fee::{self, HarvestWithheldTokensToMint};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::FitError;
+use crate::state::Challenge;
+
+/// Where a challenge's stake is held: the SOL vault PDA for SOL challenges, or the
+/// escrow token account PDA for token challenges. Every instruction that moves stake
+/// goes through this, so settlement code never cares which asset it is paying out.
+#[derive(Accounts)]
+pub struct Escrow<'info> {
+    /// SOL vault PDA `["vault", challenge]`, checked against the challenge's stored bump
+    #[account(mut)]
+    pub vault: SystemAccount<'info>,
+
+    /// Token challenges only: must be the challenge's payment mint. Pass it writable to
+    /// `close_challenge` when the mint charges transfer fees, so withheld fees can be harvested.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token challenges only: escrow token account PDA `["escrow_token", challenge]`
+    #[account(mut)]
+    pub token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The escrow of one challenge, resolved and checked against it
+enum Holding<'a, 'info> {
+    Sol {
+        vault: &'a SystemAccount<'info>,
+        rent_reserve: u64,
+    },
+    Token {
+        mint: &'a InterfaceAccount<'info, Mint>,
+        account: &'a InterfaceAccount<'info, TokenAccount>,
+        token_program: &'a Interface<'info, TokenInterface>,
+    },
+}
+
+impl Holding<'_, '_> {
+    /// Lamports in the vault above its rent reserve, or tokens in the escrow token account
+    fn balance(&self) -> u64 {
+        match self {
+            Holding::Sol { vault, rent_reserve } => vault.lamports().saturating_sub(*rent_reserve),
+            Holding::Token { account, .. } => account.amount,
+        }
+    }
+}
+
+impl<'info> Escrow<'info> {
+    /// Move `amount` from `payer` into escrow and return what actually arrived.
+    /// Transfer-fee mints deliver less than `amount`, so callers credit the return value.
+    pub fn deposit(
+        &mut self,
+        challenge: &Account<'info, Challenge>,
+        payer: &Signer<'info>,
+        payer_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<u64> {
+        let before = match self.resolve(challenge)? {
+            Holding::Sol { vault, .. } => {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: payer.to_account_info(),
+                            to: vault.to_account_info(),
+                        },
+                    ),
+                    amount,
+                )?;
+                return Ok(amount);
+            }
+            Holding::Token { mint, account, token_program } => {
+                let from = payer_token_account.ok_or(FitError::MissingEscrowAccounts)?;
+                require_keys_eq!(from.mint, mint.key(), FitError::WrongMint);
+                require_keys_eq!(from.owner, payer.key(), FitError::WrongTokenOwner);
+
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: from.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: account.to_account_info(),
+                            authority: payer.to_account_info(),
+                        },
+                    ),
+                    amount,
+                    mint.decimals,
+                )?;
+                account.amount
+            }
+        };
+
+        let account = self.token_account.as_mut().ok_or(FitError::MissingEscrowAccounts)?;
+        account.reload()?;
+        Ok(account.amount.checked_sub(before).ok_or(FitError::MathOverflow)?)
+    }
+
+    /// Pay `amount` out of escrow to `recipient`: lamports straight to the wallet for
+    /// SOL challenges, tokens to `recipient_token_account` (owned by it) otherwise.
+    /// Refuses to move anything if the escrow holds less than the challenge's books say.
+    pub fn pay(
+        &self,
+        challenge: &Account<'info, Challenge>,
+        recipient: &AccountInfo<'info>,
+        recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        let challenge_key = challenge.key();
+        let holding = self.resolve(challenge)?;
+
+        // Anyone can send stray lamports or tokens to the escrow, so a surplus is tolerated;
+        // a shortfall means the tracked pools have drifted from the funds actually held
+        let tracked = challenge.escrowed()?;
+        require!(holding.balance() >= tracked, FitError::EscrowMismatch);
+
+        match holding {
+            Holding::Sol { vault, .. } => {
+                let seeds = &[b"vault", challenge_key.as_ref(), &[challenge.vault_bump]];
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: vault.to_account_info(),
+                            to: recipient.clone(),
+                        },
+                        &[&seeds[..]],
+                    ),
+                    amount,
+                )
+            }
+            Holding::Token { mint, account, token_program } => {
+                let to = recipient_token_account.ok_or(FitError::MissingEscrowAccounts)?;
+                require_keys_eq!(to.mint, mint.key(), FitError::WrongMint);
+                require_keys_eq!(to.owner, recipient.key(), FitError::WrongTokenOwner);
+
+                let seeds = &[b"escrow_token", challenge_key.as_ref(), &[challenge.escrow_bump]];
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: account.to_account_info(),
+                        },
+                        &[&seeds[..]],
+                    ),
+                    amount,
+                    mint.decimals,
+                )
+            }
+        }
+    }
+
+    /// What the escrow holds beyond the challenge's tracked pools, e.g. unsolicited transfers
+    pub fn surplus(&self, challenge: &Account<'info, Challenge>) -> Result<u64> {
+        let balance = self.resolve(challenge)?.balance();
+        Ok(balance.checked_sub(challenge.escrowed()?).ok_or(FitError::EscrowMismatch)?)
+    }
+
+    /// Require the escrow to be drained and return its rent to `destination`: the SOL
+    /// vault's reserve is transferred out, a token escrow account is closed
+    pub fn close(
+        &self,
+        challenge: &Account<'info, Challenge>,
+        destination: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let challenge_key = challenge.key();
+        let holding = self.resolve(challenge)?;
+        require!(holding.balance() == 0, FitError::EscrowNotEmpty);
+
+        match holding {
+            Holding::Sol { vault, .. } => {
+                let seeds = &[b"vault", challenge_key.as_ref(), &[challenge.vault_bump]];
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: vault.to_account_info(),
+                            to: destination.clone(),
+                        },
+                        &[&seeds[..]],
+                    ),
+                    vault.lamports(),
+                )
+            }
+            Holding::Token { mint, account, token_program } => {
+                // Token-2022 won't close an account holding withheld transfer fees, so
+                // move them to the mint first, where the mint's fee authority collects them
+                if withheld_fees(&account.to_account_info())? > 0 {
+                    require!(mint.to_account_info().is_writable, FitError::MintNotWritable);
+                    transfer_fee::harvest_withheld_tokens_to_mint(
+                        CpiContext::new(
+                            token_program.to_account_info(),
+                            HarvestWithheldTokensToMint {
+                                token_program_id: token_program.to_account_info(),
+                                mint: mint.to_account_info(),
+                            },
+                        ),
+                        vec![account.to_account_info()],
+                    )?;
+                }
+
+                let seeds = &[b"escrow_token", challenge_key.as_ref(), &[challenge.escrow_bump]];
+                token_interface::close_account(CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    CloseAccount {
+                        account: account.to_account_info(),
+                        destination: destination.clone(),
+                        authority: account.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ))
+            }
+        }
+    }
+
+    /// Pick the escrow matching the challenge's payment type and check its address
+    fn resolve(&self, challenge: &Account<'info, Challenge>) -> Result<Holding<'_, 'info>> {
+        let challenge_key = challenge.key();
+
+        let Some(payment_mint) = challenge.payment_mint else {
+            let vault = Pubkey::create_program_address(
+                &[b"vault", challenge_key.as_ref(), &[challenge.vault_bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(FitError::InvalidEscrow))?;
+            require_keys_eq!(self.vault.key(), vault, FitError::InvalidEscrow);
+            return Ok(Holding::Sol { vault: &self.vault, rent_reserve: challenge.rent_reserve });
+        };
+
+        let mint = self.mint.as_ref().ok_or(FitError::MissingEscrowAccounts)?;
+        let account = self.token_account.as_ref().ok_or(FitError::MissingEscrowAccounts)?;
+        let token_program = self.token_program.as_ref().ok_or(FitError::MissingEscrowAccounts)?;
+        require_keys_eq!(mint.key(), payment_mint, FitError::WrongMint);
+
+        let escrow = Pubkey::create_program_address(
+            &[b"escrow_token", challenge_key.as_ref(), &[challenge.escrow_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(FitError::InvalidEscrow))?;
+        require_keys_eq!(account.key(), escrow, FitError::InvalidEscrow);
+
+        Ok(Holding::Token { mint, account, token_program })
+    }
+}
+
+/// Transfer fees withheld on a Token-2022 account (always 0 for SPL Token accounts)
+fn withheld_fees(account: &AccountInfo) -> Result<u64> {
+    if *account.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |fee| u64::from(fee.withheld_amount)))
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ChallengeType, SettlementMode, TiePolicy};
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub paused: bool,
+}
+
+#[event]
+pub struct AcceptedMintUpdated {
+    pub mint: Pubkey,
+    pub min_entry_fee: u64,
+    pub removed: bool,
+}
+
+#[event]
+pub struct ChallengeCreated {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub index: u64,
+    pub entry_fee: u64,
+    pub challenge_type: ChallengeType,
+    pub goal: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub start_time: i64,
+    pub join_deadline: i64,
+    pub end_time: i64,
+    pub submission_deadline: i64,
+    pub min_participants: u32,
+    pub max_participants: u32,
+    pub has_waitlist: bool,
+    pub leave_penalty_bps: Option<u16>,
+    pub is_public: bool,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub settlement_mode: SettlementMode,
+    pub tie_policy: TiePolicy,
+    pub payout_bps: Vec<u16>,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct ParticipantJoined {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub entry_fee: u64,
+    pub deposit: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub waitlisted: bool,
+}
+
+#[event]
+pub struct ScoreSubmitted {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub score: u64,
+    pub oracle: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeEnded {
+    pub challenge: Pubkey,
+    pub winner: Pubkey,
+    pub settlement_mode: SettlementMode,
+    pub tie_policy: TiePolicy,
+    pub award_count: u32,
+    pub payout: u64,
+    pub platform_fee: u64,
+    pub dust: u64,
+    pub cranker: Pubkey,
+    pub crank_reward: u64,
+}
+
+/// One per award at finalization; `place` 0 is a stake returned because nobody won
+#[event]
+pub struct AwardRecorded {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub place: u8,
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub place: u8,
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct PlatformFeesClaimed {
+    pub challenge: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct CrankRewardClaimed {
+    pub challenge: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct ChallengeExpired {
+    pub challenge: Pubkey,
+    pub settlement_deadline: i64,
+}
+
+#[event]
+pub struct ChallengeCancelled {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub participant_count: u32,
+}
+
+#[event]
+pub struct ChallengeUndersubscribed {
+    pub challenge: Pubkey,
+    pub participant_count: u32,
+    pub min_participants: u32,
+}
+
+#[event]
+pub struct ParticipantLeft {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub refund: u64,
+    pub penalty: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub promoted: Option<Pubkey>,
+}
+
+#[event]
+pub struct ParticipantClosed {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct ChallengeClosed {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+}
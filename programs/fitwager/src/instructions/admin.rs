@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::errors::FitError;
+use crate::events::{AcceptedMintUpdated, ConfigUpdated};
+use crate::state::{AcceptedMint, ProgramConfig};
+
+/// Initialize the global program config (only the program upgrade authority)
+pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    params.validate()?;
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury = params.treasury;
+    config.fee_bps = params.fee_bps;
+    config.accepted_mints = Vec::new();
+    config.min_entry_fee_sol = params.min_entry_fee_sol;
+    config.settle_grace_seconds = params.settle_grace_seconds;
+    config.crank_reward_bps = params.crank_reward_bps;
+    config.settlement_window_seconds = params.settlement_window_seconds;
+    config.oracles = params.oracles;
+    config.paused = false;
+    config.bump = ctx.bumps.config;
+
+    emit!(ConfigUpdated {
+        admin: config.admin,
+        treasury: config.treasury,
+        fee_bps: config.fee_bps,
+        paused: config.paused,
+    });
+
+    Ok(())
+}
+
+/// Update the global program config (only admin)
+/// Fields left as `None` keep their current value
+pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(admin) = params.admin {
+        config.admin = admin;
+    }
+    if let Some(treasury) = params.treasury {
+        config.treasury = treasury;
+    }
+    if let Some(fee_bps) = params.fee_bps {
+        require!(fee_bps <= MAX_FEE_BPS, FitError::InvalidFee);
+        config.fee_bps = fee_bps;
+    }
+    if let Some(min_entry_fee_sol) = params.min_entry_fee_sol {
+        config.min_entry_fee_sol = min_entry_fee_sol;
+    }
+    if let Some(settle_grace_seconds) = params.settle_grace_seconds {
+        require!(settle_grace_seconds >= 0, FitError::InvalidDuration);
+        config.settle_grace_seconds = settle_grace_seconds;
+    }
+    if let Some(crank_reward_bps) = params.crank_reward_bps {
+        require!(crank_reward_bps as u64 <= BPS_DENOMINATOR, FitError::InvalidFee);
+        config.crank_reward_bps = crank_reward_bps;
+    }
+    if let Some(settlement_window_seconds) = params.settlement_window_seconds {
+        config.settlement_window_seconds = settlement_window_seconds;
+    }
+    // Cranks need time to settle before challenges expire
+    require!(
+        config.settlement_window_seconds > config.settle_grace_seconds,
+        FitError::InvalidDuration
+    );
+    if let Some(oracles) = params.oracles {
+        require!(oracles.len() <= MAX_ORACLES, FitError::TooManyOracles);
+        config.oracles = oracles;
+    }
+    if let Some(paused) = params.paused {
+        config.paused = paused;
+    }
+
+    emit!(ConfigUpdated {
+        admin: config.admin,
+        treasury: config.treasury,
+        fee_bps: config.fee_bps,
+        paused: config.paused,
+    });
+
+    Ok(())
+}
+
+/// Accept a mint for entry fees, or update its minimum (only admin)
+/// `min_entry_fee` is in the mint's base units
+pub fn set_accepted_mint(ctx: Context<SetAcceptedMint>, min_entry_fee: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let mint = &ctx.accounts.mint;
+
+    let entry = AcceptedMint { mint: mint.key(), min_entry_fee };
+    match config.accepted_mints.iter_mut().find(|m| m.mint == entry.mint) {
+        Some(existing) => *existing = entry.clone(),
+        None => {
+            require!(
+                config.accepted_mints.len() < MAX_ACCEPTED_MINTS,
+                FitError::TooManyMints
+            );
+            config.accepted_mints.push(entry.clone());
+        }
+    }
+
+    emit!(AcceptedMintUpdated {
+        mint: entry.mint,
+        min_entry_fee,
+        removed: false,
+    });
+
+    Ok(())
+}
+
+/// Stop accepting a mint for new challenges and joins (only admin)
+pub fn remove_accepted_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let index = config
+        .accepted_mints
+        .iter()
+        .position(|m| m.mint == mint)
+        .ok_or(FitError::MintNotAccepted)?;
+    let removed = config.accepted_mints.remove(index);
+
+    emit!(AcceptedMintUpdated {
+        mint,
+        min_entry_fee: removed.min_entry_fee,
+        removed: true,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::SIZE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Fitwager>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ FitError::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ FitError::NotAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptedMint<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ FitError::NotAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub min_entry_fee_sol: u64,
+    pub settle_grace_seconds: i64,
+    pub crank_reward_bps: u16,
+    pub settlement_window_seconds: i64,
+    pub oracles: Vec<Pubkey>,
+}
+
+impl ConfigParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.fee_bps <= MAX_FEE_BPS, FitError::InvalidFee);
+        require!(self.oracles.len() <= MAX_ORACLES, FitError::TooManyOracles);
+        require!(self.settle_grace_seconds >= 0, FitError::InvalidDuration);
+        require!(
+            self.settlement_window_seconds > self.settle_grace_seconds,
+            FitError::InvalidDuration
+        );
+        require!(self.crank_reward_bps as u64 <= BPS_DENOMINATOR, FitError::InvalidFee);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigParams {
+    pub admin: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub fee_bps: Option<u16>,
+    pub min_entry_fee_sol: Option<u64>,
+    pub settle_grace_seconds: Option<i64>,
+    pub crank_reward_bps: Option<u16>,
+    pub settlement_window_seconds: Option<i64>,
+    pub oracles: Option<Vec<Pubkey>>,
+    pub paused: Option<bool>,
+}
+
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::FitError;
+use crate::escrow::*;
+use crate::events::{ChallengeCancelled, ChallengeExpired, ChallengeUndersubscribed, RefundClaimed};
+use crate::state::*;
+
+/// Cancel a challenge (creator before it starts or while nobody has joined, admin at any
+/// time). Participants reclaim their entry fees with `claim_refund`
+pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+
+    challenge.advance_status(clock.unix_timestamp);
+    require!(challenge.is_open(), FitError::ChallengeClosed);
+    if authority != ctx.accounts.config.admin {
+        require!(challenge.creator == authority, FitError::NotCreator);
+        // Once it is running the wager is binding: a losing creator can't void it
+        require!(
+            challenge.status == ChallengeStatus::Pending
+                || (challenge.participant_count == 0 && challenge.waitlist_count == 0),
+            FitError::CancelNotAllowed
+        );
+    }
+
+    challenge.status = ChallengeStatus::Cancelled;
+
+    emit!(ChallengeCancelled {
+        challenge: challenge.key(),
+        creator: challenge.creator,
+        cancelled_by: authority,
+        participant_count: challenge.participant_count,
+    });
+
+    Ok(())
+}
+
+/// Refund a participant's deposit from a cancelled challenge, or from one that was
+/// never settled before its settlement deadline
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let participant = &mut ctx.accounts.participant;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if challenge.advance_status(now) {
+        emit!(ChallengeUndersubscribed {
+            challenge: challenge.key(),
+            participant_count: challenge.participant_count,
+            min_participants: challenge.min_participants,
+        });
+    }
+    if challenge.expire_if_overdue(now) {
+        emit!(ChallengeExpired {
+            challenge: challenge.key(),
+            settlement_deadline: challenge.settlement_deadline,
+        });
+    }
+    // Players still on the waitlist when the challenge starts can no longer be promoted
+    require!(
+        challenge.is_refundable()
+            || (participant.waitlisted && challenge.status != ChallengeStatus::Pending),
+        FitError::NotRefundable
+    );
+    require!(participant.has_joined, FitError::NotJoined);
+    require!(!participant.refunded, FitError::AlreadyRefunded);
+
+    let amount = challenge.take_refund(participant)?;
+    ctx.accounts.escrow.pay(
+        challenge,
+        &ctx.accounts.player.to_account_info(),
+        ctx.accounts.player_token_account.as_ref(),
+        amount,
+    )?;
+
+    participant.refunded = true;
+
+    emit!(RefundClaimed {
+        challenge: challenge.key(),
+        player: participant.player,
+        amount,
+        payment_mint: challenge.payment_mint,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelChallenge<'info> {
+    /// Challenge creator or config admin
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), player.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Token challenges only: the player's account the deposit is returned to
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub escrow: Escrow<'info>,
+}
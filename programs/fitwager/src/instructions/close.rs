@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FitError;
+use crate::escrow::*;
+use crate::events::{ChallengeClosed, ParticipantClosed};
+use crate::state::*;
+
+/// Close a participant account once its payout or refund is settled.
+/// Rent goes back to the player, who paid for it on join.
+pub fn close_participant(ctx: Context<CloseParticipant>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let participant = &ctx.accounts.participant;
+
+    let settled = match challenge.status {
+        _ if participant.waitlisted => participant.refunded,
+        ChallengeStatus::Ended => participant.payout == 0 || participant.has_claimed,
+        ChallengeStatus::Cancelled | ChallengeStatus::Expired => participant.refunded,
+        _ => false,
+    };
+    require!(settled, FitError::ParticipantNotSettled);
+
+    challenge.closed_participants =
+        challenge.closed_participants.checked_add(1).ok_or(FitError::MathOverflow)?;
+
+    emit!(ParticipantClosed {
+        challenge: challenge.key(),
+        player: participant.player,
+    });
+
+    Ok(())
+}
+
+/// Close a settled challenge and its escrow, returning rent to the creator.
+/// Requires every participant account closed and the escrow drained.
+pub fn close_challenge(ctx: Context<CloseChallenge>) -> Result<()> {
+    let challenge = &ctx.accounts.challenge;
+
+    require!(
+        matches!(
+            challenge.status,
+            ChallengeStatus::Ended | ChallengeStatus::Cancelled | ChallengeStatus::Expired
+        ),
+        FitError::ChallengeNotFinalized
+    );
+    let joined = challenge
+        .participant_count
+        .checked_add(challenge.waitlist_count)
+        .ok_or(FitError::MathOverflow)?;
+    require!(challenge.closed_participants == joined, FitError::ParticipantsStillOpen);
+    require!(
+        challenge.platform_fee == 0 && challenge.crank_reward == 0,
+        FitError::EscrowNotEmpty
+    );
+    // The waitlist PDA can't be closed once the challenge is gone
+    require!(
+        !challenge.has_waitlist || ctx.accounts.waitlist.is_some(),
+        FitError::MissingWaitlist
+    );
+    // Also returns the SOL vault's rent reserve or closes the escrow token account
+    ctx.accounts.escrow.close(challenge, &ctx.accounts.creator.to_account_info())?;
+
+    emit!(ChallengeClosed {
+        challenge: challenge.key(),
+        creator: challenge.creator,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseParticipant<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"participant", challenge.key().as_ref(), player.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+}
+
+#[derive(Accounts)]
+pub struct CloseChallenge<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        constraint = challenge.creator == creator.key() @ FitError::NotCreator
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// Must be drained; its rent goes back to the creator along with the challenge's
+    pub escrow: Escrow<'info>,
+
+    /// Required for challenges created with a waitlist: closed along with the challenge
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"waitlist", challenge.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+}
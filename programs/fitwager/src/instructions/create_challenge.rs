@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FitError;
+use crate::events::ChallengeCreated;
+use crate::settlement::validate_payout_schedule;
+use crate::state::*;
+
+/// Create a new fitness challenge
+/// Supports SOL and any allowlisted SPL / Token-2022 mint for entry fees
+pub fn create_challenge(
+    ctx: Context<CreateChallenge>,
+    params: CreateChallengeParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let challenge = &mut ctx.accounts.challenge;
+    let clock = Clock::get()?;
+
+    let CreateChallengeParams {
+        entry_fee,
+        start_time,
+        duration_seconds,
+        join_deadline,
+        submission_grace_seconds,
+        min_participants,
+        max_participants,
+        leave_penalty_bps,
+        challenge_type,
+        goal,
+        payment_mint,
+        is_public,
+        allowlist_root,
+        settlement_mode,
+        tie_policy,
+        payout_bps,
+    } = params;
+
+    require!(!config.paused, FitError::ProgramPaused);
+    require!(entry_fee > 0, FitError::InvalidEntryFee);
+    require!(entry_fee >= config.min_entry_fee(payment_mint)?, FitError::EntryFeeTooSmall);
+    require!(duration_seconds > 0, FitError::InvalidDuration);
+
+    // Scheduled challenges start later; joins close at `join_deadline` (default: end)
+    let start_time = start_time.unwrap_or(clock.unix_timestamp);
+    require!(start_time >= clock.unix_timestamp, FitError::InvalidSchedule);
+    let end_time = start_time.checked_add(duration_seconds).ok_or(FitError::MathOverflow)?;
+    let join_deadline = join_deadline.unwrap_or(end_time);
+    require!(
+        join_deadline > clock.unix_timestamp && join_deadline <= end_time,
+        FitError::InvalidSchedule
+    );
+    require!(
+        (0..=MAX_SUBMISSION_GRACE_SECONDS).contains(&submission_grace_seconds),
+        FitError::InvalidDuration
+    );
+    require!(goal > 0, FitError::InvalidGoal);
+    // Every challenge is capped so it can always be settled in one transaction
+    let max_participants = max_participants.unwrap_or(MAX_PARTICIPANTS);
+    require!(
+        max_participants > 0 && max_participants <= MAX_PARTICIPANTS,
+        FitError::InvalidParticipantCap
+    );
+    require!(
+        min_participants >= 2 && min_participants <= max_participants,
+        FitError::InvalidMinParticipants
+    );
+    require!(
+        leave_penalty_bps.unwrap_or(0) as u64 <= BPS_DENOMINATOR,
+        FitError::InvalidPenalty
+    );
+    // Private challenges are invite-only: they need an allowlist, public ones can't have one
+    require!(is_public != allowlist_root.is_some(), FitError::InvalidAllowlist);
+    if settlement_mode == SettlementMode::Podium {
+        validate_payout_schedule(&payout_bps)?;
+    } else {
+        require!(payout_bps.is_empty(), FitError::InvalidPayoutSchedule);
+    }
+
+    // Token challenges escrow an allowlisted mint in a token account created above
+    if let Some(payment_mint) = payment_mint {
+        let mint = ctx.accounts.mint.as_ref().ok_or(FitError::MissingEscrowAccounts)?;
+        require_keys_eq!(mint.key(), payment_mint, FitError::WrongMint);
+        require!(ctx.accounts.escrow_token_account.is_some(), FitError::MissingEscrowAccounts);
+    } else {
+        require!(
+            ctx.accounts.escrow_token_account.is_none() && ctx.accounts.mint.is_none(),
+            FitError::UnexpectedTokenAccounts
+        );
+    }
+
+    // The SOL vault is funded to rent exemption up front so partial payouts never leave it
+    // below the minimum; the creator gets the reserve back in `close_challenge`
+    let rent_reserve = if payment_mint.is_none() {
+        let rent_reserve = Rent::get()?.minimum_balance(0);
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.escrow_vault.to_account_info(),
+                },
+            ),
+            rent_reserve,
+        )?;
+        rent_reserve
+    } else {
+        0
+    };
+
+    if let Some(waitlist) = ctx.accounts.waitlist.as_mut() {
+        waitlist.challenge = challenge.key();
+        waitlist.players = Vec::new();
+        waitlist.bump = ctx.bumps.waitlist.ok_or(FitError::MissingWaitlist)?;
+    }
+
+    // The creator's counter seeds the challenge PDA, so bump it for the next one
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.creator = ctx.accounts.creator.key();
+    creator_profile.bump = ctx.bumps.creator_profile;
+    let index = creator_profile.challenge_count;
+    creator_profile.challenge_count =
+        index.checked_add(1).ok_or(FitError::MathOverflow)?;
+
+    challenge.creator = ctx.accounts.creator.key();
+    challenge.index = index;
+    challenge.entry_fee = entry_fee;
+    challenge.start_time = start_time;
+    challenge.end_time = end_time;
+    challenge.join_deadline = join_deadline;
+    challenge.submission_deadline =
+        end_time.checked_add(submission_grace_seconds).ok_or(FitError::MathOverflow)?;
+    challenge.settlement_deadline = challenge
+        .submission_deadline
+        .checked_add(config.settlement_window_seconds)
+        .ok_or(FitError::MathOverflow)?;
+    // Players stake under the fee terms in force now; later config updates don't apply
+    challenge.fee_bps = config.fee_bps;
+    challenge.crank_reward_bps = config.crank_reward_bps;
+    challenge.settle_grace_seconds = config.settle_grace_seconds;
+    challenge.total_pool = 0;
+    challenge.participant_count = 0;
+    challenge.closed_participants = 0;
+    challenge.min_participants = min_participants;
+    challenge.max_participants = max_participants;
+    challenge.has_waitlist = ctx.accounts.waitlist.is_some();
+    challenge.waitlist_count = 0;
+    challenge.waitlist_pool = 0;
+    challenge.leave_penalty_bps = leave_penalty_bps;
+    challenge.forfeited = 0;
+    challenge.rent_reserve = rent_reserve;
+    challenge.status = if start_time > clock.unix_timestamp {
+        ChallengeStatus::Pending
+    } else {
+        ChallengeStatus::Active
+    };
+    challenge.challenge_type = challenge_type;
+    challenge.goal = goal;
+    challenge.payment_mint = payment_mint;
+    challenge.is_public = is_public;
+    challenge.allowlist_root = allowlist_root;
+    challenge.winner = Pubkey::default();
+    challenge.settlement_mode = settlement_mode;
+    challenge.tie_policy = tie_policy;
+    challenge.payout_bps = payout_bps;
+    challenge.platform_fee = 0;
+    challenge.cranker = Pubkey::default();
+    challenge.crank_reward = 0;
+    challenge.vault_bump = ctx.bumps.escrow_vault;
+    challenge.escrow_bump = ctx.bumps.escrow_token_account.unwrap_or_default();
+    challenge.bump = ctx.bumps.challenge;
+
+    emit!(ChallengeCreated {
+        challenge: challenge.key(),
+        creator: ctx.accounts.creator.key(),
+        index,
+        entry_fee,
+        challenge_type,
+        goal,
+        payment_mint,
+        start_time,
+        join_deadline,
+        end_time: challenge.end_time,
+        submission_deadline: challenge.submission_deadline,
+        min_participants,
+        max_participants,
+        has_waitlist: challenge.has_waitlist,
+        leave_penalty_bps,
+        is_public,
+        allowlist_root,
+        settlement_mode,
+        tie_policy,
+        payout_bps: challenge.payout_bps.clone(),
+        fee_bps: challenge.fee_bps,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateChallenge<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorProfile::SIZE,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Challenge::SIZE,
+        seeds = [
+            b"challenge",
+            creator.key().as_ref(),
+            &creator_profile.challenge_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// CHECK: PDA for holding SOL, funded to rent exemption by the creator
+    #[account(
+        mut,
+        seeds = [b"vault", challenge.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    /// Token challenges only: must be the challenge's payment mint
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token challenges only: escrow token account owned by itself
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"escrow_token", challenge.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow_token_account,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional queue for players joining once the cap is reached
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Waitlist::SIZE,
+        seeds = [b"waitlist", challenge.key().as_ref()],
+        bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateChallengeParams {
+    pub entry_fee: u64,
+    /// Scheduled start, `None` to start immediately
+    pub start_time: Option<i64>,
+    pub duration_seconds: i64,
+    /// Last moment to join, `None` to allow joins until `end_time`
+    pub join_deadline: Option<i64>,
+    /// How long after `end_time` verified scores are still accepted
+    pub submission_grace_seconds: i64,
+    /// Fewest players for the wager to go ahead (at least 2)
+    pub min_participants: u32,
+    /// Seat limit, `None` for the program maximum `MAX_PARTICIPANTS`
+    pub max_participants: Option<u32>,
+    /// Share of the deposit kept when leaving after the start, `None` to forbid it
+    pub leave_penalty_bps: Option<u16>,
+    pub challenge_type: ChallengeType,
+    pub goal: u64,
+    /// Entry fee mint, `None` for SOL. Must be in the config allowlist.
+    pub payment_mint: Option<Pubkey>,
+    pub is_public: bool,
+    /// Merkle root of invited players, required for private challenges.
+    /// See `attestation::verify_allowlist_proof` for the tree layout.
+    pub allowlist_root: Option<[u8; 32]>,
+    pub settlement_mode: SettlementMode,
+    /// How equal scores are ranked in podium mode
+    pub tie_policy: TiePolicy,
+    /// Share of the prize pool per place in basis points, e.g. [6000, 3000, 1000].
+    /// Podium mode only; must be empty for goal-split modes.
+    pub payout_bps: Vec<u16>,
+}
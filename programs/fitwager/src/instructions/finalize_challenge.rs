@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FitError;
+use crate::events::{ChallengeEnded, ChallengeUndersubscribed};
+use crate::settlement::{bps_of, compute_awards, load_participants, split_dust, winner};
+use crate::state::*;
+
+/// Finalize a challenge: compute results and record what each participant
+/// and the treasury are owed. Funds are withdrawn with the `claim_*` instructions.
+/// The creator can finalize at `submission_deadline`; anyone else can once the settlement grace
+/// period has passed, earning the crank reward out of the platform fee. Fee, crank reward and
+/// grace period are the config values in force when the challenge was created.
+/// Remaining accounts: every `Participant` PDA of the challenge (writable). At most
+/// `MAX_PARTICIPANTS`, which keeps this within a legacy transaction.
+pub fn finalize_challenge<'info>(
+    ctx: Context<'_, '_, '_, 'info, FinalizeChallenge<'info>>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let challenge = &mut ctx.accounts.challenge;
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+
+    require!(!config.paused, FitError::ProgramPaused);
+    require!(
+        clock.unix_timestamp >= challenge.submission_deadline,
+        FitError::ChallengeNotOver
+    );
+    // Too few players by the end: cancel instead of settling, so nobody pays a fee
+    if challenge.advance_status(clock.unix_timestamp) {
+        emit!(ChallengeUndersubscribed {
+            challenge: challenge.key(),
+            participant_count: challenge.participant_count,
+            min_participants: challenge.min_participants,
+        });
+        return Ok(());
+    }
+    require!(challenge.status == ChallengeStatus::Active, FitError::ChallengeClosed);
+    require!(
+        clock.unix_timestamp < challenge.settlement_deadline,
+        FitError::SettlementExpired
+    );
+
+    let is_crank = authority != challenge.creator;
+    if is_crank {
+        let grace_end = challenge
+            .submission_deadline
+            .checked_add(challenge.settle_grace_seconds)
+            .ok_or(FitError::MathOverflow)?;
+        require!(clock.unix_timestamp >= grace_end, FitError::SettlementGracePeriod);
+    }
+
+    // Winners are computed from the submitted scores, not chosen by the caller
+    let mut participants = load_participants(
+        &challenge.key(),
+        challenge.participant_count,
+        ctx.remaining_accounts,
+    )?;
+
+    // Calculate payout (platform fee fixed at creation, remainder split per settlement mode)
+    let platform_fee = bps_of(challenge.total_pool, challenge.fee_bps)?;
+    let prize_pool = challenge.total_pool.checked_sub(platform_fee).ok_or(FitError::MathOverflow)?;
+    let awards = compute_awards(challenge, &participants, prize_pool)?;
+    let (payout, dust) = split_dust(prize_pool, &awards)?;
+    let crank_reward = if is_crank { bps_of(platform_fee, challenge.crank_reward_bps)? } else { 0 };
+
+    // Record each participant's entitlement
+    for (participant, info) in participants.iter_mut().zip(ctx.remaining_accounts) {
+        if let Some(award) = awards.iter().find(|award| award.player == participant.player) {
+            participant.place = award.place;
+            participant.payout = award.amount;
+            require!(info.is_writable, FitError::InvalidParticipant);
+            participant.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+    }
+    for award in &awards {
+        award.emit(challenge.key(), challenge.payment_mint);
+    }
+
+    challenge.status = ChallengeStatus::Ended;
+    // Every unit of the pool is owed to someone, so the escrow drains to zero
+    challenge.platform_fee = platform_fee
+        .checked_sub(crank_reward)
+        .and_then(|fee| fee.checked_add(dust))
+        .ok_or(FitError::MathOverflow)?;
+    challenge.cranker = if is_crank { authority } else { Pubkey::default() };
+    challenge.crank_reward = crank_reward;
+    challenge.winner = winner(&awards);
+
+    emit!(ChallengeEnded {
+        challenge: challenge.key(),
+        winner: challenge.winner,
+        settlement_mode: challenge.settlement_mode,
+        tie_policy: challenge.tie_policy,
+        award_count: awards.len() as u32,
+        payout,
+        platform_fee: challenge.platform_fee,
+        dust,
+        cranker: challenge.cranker,
+        crank_reward,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeChallenge<'info> {
+    /// Challenge creator, or any crank once the settlement grace period has passed
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+}
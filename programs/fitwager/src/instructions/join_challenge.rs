@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::attestation::verify_allowlist_proof;
+use crate::errors::FitError;
+use crate::escrow::*;
+use crate::events::ParticipantJoined;
+use crate::state::*;
+
+/// Join a challenge, paying the entry fee in SOL or the challenge's token
+/// `allowlist_proof` is the player's Merkle proof for private challenges (empty otherwise)
+pub fn join_challenge(ctx: Context<JoinChallenge>, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let challenge = &mut ctx.accounts.challenge;
+    let participant = &mut ctx.accounts.participant;
+    let clock = Clock::get()?;
+
+    require!(!config.paused, FitError::ProgramPaused);
+    challenge.advance_status(clock.unix_timestamp);
+    require!(challenge.is_open(), FitError::ChallengeClosed);
+    require!(!participant.has_joined, FitError::AlreadyJoined);
+    require!(clock.unix_timestamp < challenge.join_deadline, FitError::JoinClosed);
+    if let Some(root) = challenge.allowlist_root {
+        verify_allowlist_proof(&root, &ctx.accounts.player.key(), &allowlist_proof)?;
+    }
+
+    // Enforce minimum entry fee for joins (prevents joining tiny-fee challenges)
+    require!(
+        challenge.entry_fee >= config.min_entry_fee(challenge.payment_mint)?,
+        FitError::EntryFeeTooSmall
+    );
+
+    // Once full, players can only queue (with their deposit) on the waitlist
+    let waitlisted = challenge.is_full();
+    if waitlisted {
+        let waitlist = ctx.accounts.waitlist.as_mut().ok_or(FitError::ChallengeFull)?;
+        waitlist.enqueue(challenge, ctx.accounts.player.key())?;
+    }
+
+    // The pool is credited with what actually reached escrow
+    let deposit = ctx.accounts.escrow.deposit(
+        challenge,
+        &ctx.accounts.player,
+        ctx.accounts.player_token_account.as_ref(),
+        challenge.entry_fee,
+    )?;
+    challenge.credit_deposit(deposit, waitlisted)?;
+
+    participant.player = ctx.accounts.player.key();
+    participant.challenge = challenge.key();
+    participant.score = 0;
+    participant.has_joined = true;
+    participant.has_submitted = false;
+    participant.joined_at = clock.unix_timestamp;
+    participant.deposit = deposit;
+    participant.waitlisted = waitlisted;
+    participant.refunded = false;
+    participant.place = 0;
+    participant.payout = 0;
+    participant.has_claimed = false;
+    participant.bump = ctx.bumps.participant;
+
+    emit!(ParticipantJoined {
+        challenge: challenge.key(),
+        player: ctx.accounts.player.key(),
+        entry_fee: challenge.entry_fee,
+        deposit,
+        payment_mint: challenge.payment_mint,
+        waitlisted,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct JoinChallenge<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Participant::SIZE,
+        seeds = [b"participant", challenge.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Token challenges only: the player's account the entry fee is paid from
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub escrow: Escrow<'info>,
+
+    /// Required only to queue on a full challenge
+    #[account(
+        mut,
+        seeds = [b"waitlist", challenge.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::FitError;
+use crate::escrow::*;
+use crate::events::ParticipantLeft;
+use crate::state::*;
+
+/// Leave a challenge and take back the entry fee.
+/// Before the start the full deposit is returned and the head of the waitlist, if any,
+/// takes the freed seat. After the start leaving is only possible if the creator set a
+/// leave penalty, which stays in the prize pool.
+pub fn leave_challenge(ctx: Context<LeaveChallenge>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let participant = &ctx.accounts.participant;
+
+    let (refund, penalty) = challenge.withdraw(
+        participant,
+        ctx.accounts.waitlist.as_deref_mut(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    let promoted = promote_after_leave(
+        challenge,
+        participant,
+        ctx.accounts.waitlist.as_deref_mut(),
+        ctx.accounts.next_participant.as_deref_mut(),
+    )?;
+
+    ctx.accounts.escrow.pay(
+        challenge,
+        &ctx.accounts.player.to_account_info(),
+        ctx.accounts.player_token_account.as_ref(),
+        refund,
+    )?;
+
+    emit!(ParticipantLeft {
+        challenge: challenge.key(),
+        player: participant.player,
+        refund,
+        penalty,
+        payment_mint: challenge.payment_mint,
+        promoted,
+    });
+
+    Ok(())
+}
+
+/// Fill a seat freed before the start with the head of the waitlist.
+/// Returns the promoted player, if anyone was waiting.
+fn promote_after_leave(
+    challenge: &mut Challenge,
+    leaver: &Participant,
+    waitlist: Option<&mut Waitlist>,
+    next: Option<&mut Participant>,
+) -> Result<Option<Pubkey>> {
+    if leaver.waitlisted
+        || challenge.status != ChallengeStatus::Pending
+        || challenge.waitlist_count == 0
+    {
+        return Ok(None);
+    }
+    let waitlist = waitlist.ok_or(FitError::MissingWaitlist)?;
+    let next = next.ok_or(FitError::MissingWaitlist)?;
+    challenge.promote_from_waitlist(waitlist, next)?;
+    Ok(Some(next.player))
+}
+
+#[derive(Accounts)]
+pub struct LeaveChallenge<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"participant", challenge.key().as_ref(), player.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Token challenges only: the player's account the refund is paid to
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub escrow: Escrow<'info>,
+
+    /// Required while players are queued before the start
+    #[account(
+        mut,
+        seeds = [b"waitlist", challenge.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+
+    /// Head of the waitlist, promoted into the freed seat
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), next_participant.player.as_ref()],
+        bump = next_participant.bump
+    )]
+    pub next_participant: Option<Account<'info, Participant>>,
+}
@@ -0,0 +1,19 @@
+pub mod admin;
+pub mod cancel_challenge;
+pub mod close;
+pub mod create_challenge;
+pub mod finalize_challenge;
+pub mod join_challenge;
+pub mod leave_challenge;
+pub mod payout;
+pub mod submit_result;
+
+pub use admin::*;
+pub use cancel_challenge::*;
+pub use close::*;
+pub use create_challenge::*;
+pub use finalize_challenge::*;
+pub use join_challenge::*;
+pub use leave_challenge::*;
+pub use payout::*;
+pub use submit_result::*;
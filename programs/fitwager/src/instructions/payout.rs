@@ -1,33 +1,159 @@
 use anchor_lang::prelude::*;
-use crate::state::challenge::Challenge;
+use anchor_spl::token_interface::TokenAccount;
 
-pub fn handler(ctx: Context<Payout>) -> Result<()> {
+use crate::errors::FitError;
+use crate::escrow::*;
+use crate::events::{CrankRewardClaimed, PlatformFeesClaimed, WinningsClaimed};
+use crate::state::*;
+
+/// Withdraw a participant's recorded winnings or stake return
+pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let participant = &mut ctx.accounts.participant;
+
+    require!(challenge.status == ChallengeStatus::Ended, FitError::ChallengeNotFinalized);
+    require!(participant.payout > 0, FitError::NothingToClaim);
+    require!(!participant.has_claimed, FitError::AlreadyClaimed);
+
+    let amount = participant.payout;
+    ctx.accounts.escrow.pay(
+        challenge,
+        &ctx.accounts.player.to_account_info(),
+        ctx.accounts.player_token_account.as_ref(),
+        amount,
+    )?;
+
+    challenge.debit_pool(amount, false)?;
+    participant.has_claimed = true;
+
+    emit!(WinningsClaimed {
+        challenge: challenge.key(),
+        player: participant.player,
+        place: participant.place,
+        amount,
+        payment_mint: challenge.payment_mint,
+    });
+
+    Ok(())
+}
+
+/// Pay the crank reward to whoever finalized an overdue challenge
+pub fn claim_crank_reward(ctx: Context<ClaimCrankReward>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    require!(challenge.status == ChallengeStatus::Ended, FitError::ChallengeNotFinalized);
+    require!(challenge.crank_reward > 0, FitError::NothingToClaim);
+
+    let amount = challenge.crank_reward;
+    ctx.accounts.escrow.pay(
+        challenge,
+        &ctx.accounts.cranker.to_account_info(),
+        ctx.accounts.cranker_token_account.as_ref(),
+        amount,
+    )?;
+
+    challenge.debit_pool(amount, false)?;
+    challenge.crank_reward = 0;
+
+    emit!(CrankRewardClaimed {
+        challenge: challenge.key(),
+        cranker: ctx.accounts.cranker.key(),
+        amount,
+        payment_mint: challenge.payment_mint,
+    });
+
+    Ok(())
+}
+
+/// Send a finalized challenge's platform fee, rounding dust included, to the treasury.
+/// Anything sent to the escrow outside the program is swept along with it, so the
+/// escrow can always be drained to zero and closed.
+pub fn claim_platform_fees(ctx: Context<ClaimPlatformFees>) -> Result<()> {
     let challenge = &mut ctx.accounts.challenge;
 
-    // Move entire escrow balance to winner
-    let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+    let due = challenge.treasury_due()?;
+    let surplus = ctx.accounts.escrow.surplus(challenge)?;
+    let amount = due.checked_add(surplus).ok_or(FitError::MathOverflow)?;
+    require!(amount > 0, FitError::NothingToClaim);
+
+    ctx.accounts.escrow.pay(
+        challenge,
+        &ctx.accounts.treasury,
+        ctx.accounts.treasury_token_account.as_ref(),
+        amount,
+    )?;
 
-    **ctx.accounts.winner.to_account_info().try_borrow_mut_lamports()? += escrow_balance;
-    **ctx.accounts.escrow.to_account_info().try_borrow_mut_lamports()? -= escrow_balance;
+    challenge.debit_pool(due, false)?;
+    challenge.platform_fee = 0;
 
-    challenge.status = 2;
+    emit!(PlatformFeesClaimed {
+        challenge: challenge.key(),
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        payment_mint: challenge.payment_mint,
+    });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct Payout<'info> {
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), player.key().as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Token challenges only: the player's account winnings are paid to
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub escrow: Escrow<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCrankReward<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.cranker == cranker.key() @ FitError::NotCranker
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// Token challenges only: the cranker's account the reward is paid to
     #[account(mut)]
-    pub winner: Signer<'info>,
+    pub cranker_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub escrow: Escrow<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPlatformFees<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
 
     #[account(mut)]
     pub challenge: Account<'info, Challenge>,
 
-    /// CHECK: Only holds lamports
+    /// CHECK: Platform fee recipient, must be the configured treasury
     #[account(
         mut,
-        seeds = [b"escrow", challenge.key().as_ref()],
-        bump
+        address = config.treasury @ FitError::WrongTreasury
     )]
-    pub escrow: UncheckedAccount<'info>,
+    pub treasury: AccountInfo<'info>,
+
+    /// Token challenges only: the treasury's account for the challenge's mint
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub escrow: Escrow<'info>,
 }
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+
+use crate::attestation::verify_oracle_signature;
+use crate::errors::FitError;
+use crate::events::ScoreSubmitted;
+use crate::state::*;
+
+/// Submit a verified fitness score (relayed by backend after Google Fit verification)
+/// The transaction must include an Ed25519 instruction, immediately before this one,
+/// in which an authorized oracle signs the serialized `attestation`
+pub fn submit_score(ctx: Context<SubmitScore>, attestation: ScoreAttestation) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let challenge = &mut ctx.accounts.challenge;
+    let participant = &mut ctx.accounts.participant;
+    let clock = Clock::get()?;
+
+    require!(!config.paused, FitError::ProgramPaused);
+    challenge.advance_status(clock.unix_timestamp);
+    require!(challenge.status != ChallengeStatus::Pending, FitError::ChallengeNotStarted);
+    require!(challenge.status == ChallengeStatus::Active, FitError::ChallengeClosed);
+    require!(participant.has_joined, FitError::NotJoined);
+    require!(!participant.waitlisted, FitError::Waitlisted);
+    // Late-synced data is accepted until the submission deadline, but only for
+    // activity inside [start_time, end_time] (checked against the attestation below)
+    require!(
+        clock.unix_timestamp <= challenge.submission_deadline,
+        FitError::SubmissionsClosed
+    );
+
+    // The attestation must be for this participant and still fresh
+    require_keys_eq!(attestation.challenge, challenge.key(), FitError::InvalidVerification);
+    require_keys_eq!(attestation.player, participant.player, FitError::InvalidVerification);
+    require!(clock.unix_timestamp <= attestation.expires_at, FitError::AttestationExpired);
+    // Anyone can relay, so an already-used attestation must not be accepted again
+    require!(
+        attestation.nonce > participant.last_attestation_nonce,
+        FitError::AttestationReplayed
+    );
+    require!(
+        attestation.window_start <= attestation.window_end
+            && attestation.window_start >= challenge.start_time
+            && attestation.window_end <= challenge.end_time,
+        FitError::InvalidAttestationWindow
+    );
+
+    let oracle = verify_oracle_signature(
+        &ctx.accounts.instructions_sysvar,
+        &config.oracles,
+        &attestation.try_to_vec()?,
+    )?;
+
+    // Update participant score (allows multiple submissions, keeps highest).
+    // `last_submission` dates the best score, which is what the tie policy compares.
+    let score = attestation.score;
+    if score > participant.score || !participant.has_submitted {
+        participant.score = score;
+        participant.last_submission = clock.unix_timestamp;
+        participant.verification_hash = attestation.verification_hash;
+    }
+    participant.has_submitted = true;
+    participant.last_attestation_nonce = attestation.nonce;
+
+    emit!(ScoreSubmitted {
+        challenge: challenge.key(),
+        player: participant.player,
+        score,
+        oracle,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitScore<'info> {
+    /// Relays the transaction; authorization comes from the oracle signature
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), participant.player.as_ref()],
+        bump = participant.bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// CHECK: Instructions sysvar, used to read the oracle's Ed25519 signature
+    #[account(address = ix_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+/// Score attestation signed by an oracle after verifying the player's Google Fit data.
+/// The Ed25519 message is the Borsh serialization of this struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScoreAttestation {
+    pub challenge: Pubkey,
+    pub player: Pubkey,
+    pub score: u64,
+    pub verification_hash: [u8; 32],
+    pub window_start: i64,
+    pub window_end: i64,
+    pub expires_at: i64,
+    /// Unique and increasing across all oracles for a player, e.g. issue time in milliseconds.
+    /// Only a nonce above the last accepted one is taken, so each attestation is used once.
+    pub nonce: u64,
+}
@@ -0,0 +1,379 @@
+use anchor_lang::prelude::*;
+use std::cmp::Reverse;
+
+use crate::constants::*;
+use crate::errors::FitError;
+use crate::events::AwardRecorded;
+use crate::state::{Challenge, Participant, SettlementMode, TiePolicy};
+
+/// Deserialize and verify the `Participant` accounts passed for settlement.
+/// Every joined participant must be present exactly once.
+pub fn load_participants(
+    challenge: &Pubkey,
+    participant_count: u32,
+    accounts: &[AccountInfo],
+) -> Result<Vec<Participant>> {
+    require!(
+        accounts.len() == participant_count as usize,
+        FitError::MissingParticipants
+    );
+
+    let mut participants: Vec<Participant> = Vec::with_capacity(accounts.len());
+    for info in accounts {
+        require_keys_eq!(*info.owner, crate::ID, FitError::InvalidParticipant);
+        let participant = Participant::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+
+        require_keys_eq!(participant.challenge, *challenge, FitError::InvalidParticipant);
+        require!(
+            participant.has_joined && !participant.waitlisted,
+            FitError::InvalidParticipant
+        );
+
+        let expected = Pubkey::create_program_address(
+            &[
+                b"participant",
+                challenge.as_ref(),
+                participant.player.as_ref(),
+                &[participant.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(FitError::InvalidParticipant))?;
+        require_keys_eq!(expected, info.key(), FitError::InvalidParticipant);
+
+        // PDAs are unique per player, so a repeated player means a repeated account
+        require!(
+            participants.iter().all(|p| p.player != participant.player),
+            FitError::DuplicateParticipant
+        );
+        participants.push(participant);
+    }
+
+    Ok(participants)
+}
+
+/// What a participant is ranked by: score (highest first), then the `tie_policy` timestamp.
+/// Participants with equal keys are tied and split their places.
+fn rank_key(participant: &Participant, tie_policy: TiePolicy) -> (Reverse<u64>, i64) {
+    let tiebreak = match tie_policy {
+        TiePolicy::SplitEqually => 0,
+        TiePolicy::EarliestSubmission => participant.last_submission,
+        TiePolicy::EarliestJoin => participant.joined_at,
+    };
+    (Reverse(participant.score), tiebreak)
+}
+
+/// Participants who submitted, in rank order. Tied participants are ordered by key so
+/// the result never depends on the order the finalizer passed the accounts in.
+fn rank_participants(participants: &[Participant], tie_policy: TiePolicy) -> Vec<&Participant> {
+    let mut ranked: Vec<&Participant> = participants.iter().filter(|p| p.has_submitted).collect();
+    ranked.sort_by_key(|p| (rank_key(p, tie_policy), p.player));
+    ranked
+}
+
+/// Nobody won: everyone gets their stake back minus the fee
+fn return_stakes(participants: &[Participant], prize_pool: u64) -> Vec<Award> {
+    let refund = prize_pool / participants.len() as u64;
+    participants
+        .iter()
+        .map(|p| Award { player: p.player, place: 0, amount: refund })
+        .collect()
+}
+
+/// `bps` basis points of `amount`, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(FitError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+    Ok(u64::try_from(share).map_err(|_| FitError::MathOverflow)?)
+}
+
+/// Split `prize_pool` across the paid places of `payout_bps`.
+/// Places without a ranked participant fold their share into first place.
+fn compute_prizes(prize_pool: u64, payout_bps: &[u16], ranked_count: usize) -> Result<Vec<u64>> {
+    let places = payout_bps.len().min(ranked_count);
+    let unfilled_bps = payout_bps[places..]
+        .iter()
+        .try_fold(0u16, |total, bps| total.checked_add(*bps))
+        .ok_or(FitError::MathOverflow)?;
+
+    (0..places)
+        .map(|place| {
+            let mut bps = payout_bps[place];
+            if place == 0 {
+                bps = bps.checked_add(unfilled_bps).ok_or(FitError::MathOverflow)?;
+            }
+            bps_of(prize_pool, bps)
+        })
+        .collect()
+}
+
+/// Hand out `prizes` down the ranking. A run of tied participants (equal `rank_key`)
+/// shares the prizes of every place it covers and all of it takes the run's top place,
+/// even when the run extends past the paid places.
+fn podium_awards(
+    ranked: &[&Participant],
+    prizes: &[u64],
+    tie_policy: TiePolicy,
+) -> Result<Vec<Award>> {
+    let mut awards = Vec::with_capacity(prizes.len());
+    let mut start = 0;
+    while start < prizes.len() {
+        let key = rank_key(ranked[start], tie_policy);
+        let mut end = start + 1;
+        while end < ranked.len() && rank_key(ranked[end], tie_policy) == key {
+            end += 1;
+        }
+
+        let shared = prizes[start..end.min(prizes.len())]
+            .iter()
+            .try_fold(0u64, |total, prize| total.checked_add(*prize))
+            .ok_or(FitError::MathOverflow)?;
+        let amount = shared / (end - start) as u64;
+        awards.extend(ranked[start..end].iter().map(|p| Award {
+            player: p.player,
+            place: start as u8 + 1,
+            amount,
+        }));
+        start = end;
+    }
+    Ok(awards)
+}
+
+/// A settlement entitlement owed to one player
+pub struct Award {
+    pub player: Pubkey,
+    // 1-based place for prizes, 0 when the stake is returned because nobody won
+    pub place: u8,
+    pub amount: u64,
+}
+
+impl Award {
+    pub fn emit(&self, challenge: Pubkey, payment_mint: Option<Pubkey>) {
+        emit!(AwardRecorded {
+            challenge,
+            player: self.player,
+            place: self.place,
+            amount: self.amount,
+            payment_mint,
+        });
+    }
+}
+
+/// Work out who is paid what from `prize_pool` (the pool after the platform fee).
+/// Awards come out in rank order for podium mode and in participant order otherwise.
+/// Every split rounds down; what is left over is handled by `split_dust`.
+pub fn compute_awards(
+    challenge: &Challenge,
+    participants: &[Participant],
+    prize_pool: u64,
+) -> Result<Vec<Award>> {
+    require!(!participants.is_empty(), FitError::NoSubmissions);
+
+    if challenge.settlement_mode == SettlementMode::Podium {
+        let ranked = rank_participants(participants, challenge.tie_policy);
+        // Nobody submitted a score
+        if ranked.is_empty() {
+            return Ok(return_stakes(participants, prize_pool));
+        }
+        let prizes = compute_prizes(prize_pool, &challenge.payout_bps, ranked.len())?;
+        return podium_awards(&ranked, &prizes, challenge.tie_policy);
+    }
+
+    let achievers: Vec<&Participant> = participants
+        .iter()
+        .filter(|p| p.has_submitted && p.score >= challenge.goal)
+        .collect();
+
+    // Nobody reached the goal
+    if achievers.is_empty() {
+        return Ok(return_stakes(participants, prize_pool));
+    }
+
+    let total_score: u128 = achievers.iter().map(|p| p.score as u128).sum();
+    achievers
+        .iter()
+        .map(|p| {
+            let amount = match challenge.settlement_mode {
+                SettlementMode::GoalSplitProRata => (prize_pool as u128)
+                    .checked_mul(p.score as u128)
+                    .and_then(|weighted| weighted.checked_div(total_score))
+                    .and_then(|amount| u64::try_from(amount).ok())
+                    .ok_or(FitError::MathOverflow)?,
+                _ => prize_pool / achievers.len() as u64,
+            };
+            Ok(Award { player: p.player, place: 1, amount })
+        })
+        .collect()
+}
+
+/// The player recorded as `Challenge::winner`: the best-placed award with the largest
+/// amount, ties going to the lowest key so the choice is independent of account order
+pub fn winner(awards: &[Award]) -> Pubkey {
+    awards
+        .iter()
+        .filter(|award| award.place > 0)
+        .min_by_key(|award| (award.place, Reverse(award.amount), award.player))
+        .map_or(Pubkey::default(), |award| award.player)
+}
+
+/// Total the awards and return `(payout, dust)`, where dust is the rounding remainder of
+/// `prize_pool` that no award covers. Dust goes to the treasury with the platform fee,
+/// which keeps the policy the same for every settlement mode.
+pub fn split_dust(prize_pool: u64, awards: &[Award]) -> Result<(u64, u64)> {
+    let payout = awards
+        .iter()
+        .try_fold(0u64, |total, award| total.checked_add(award.amount))
+        .ok_or(FitError::MathOverflow)?;
+    let dust = prize_pool.checked_sub(payout).ok_or(FitError::MathOverflow)?;
+    Ok((payout, dust))
+}
+
+/// Payout schedule must have 1..=MAX_PAYOUT_PLACES non-zero places summing to 100%
+pub fn validate_payout_schedule(payout_bps: &[u16]) -> Result<()> {
+    require!(
+        !payout_bps.is_empty() && payout_bps.len() <= MAX_PAYOUT_PLACES,
+        FitError::InvalidPayoutSchedule
+    );
+    require!(payout_bps.iter().all(|bps| *bps > 0), FitError::InvalidPayoutSchedule);
+    let total: u64 = payout_bps.iter().map(|bps| *bps as u64).sum();
+    require!(total == BPS_DENOMINATOR, FitError::InvalidPayoutSchedule);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::challenge::tests::challenge;
+    use crate::state::ChallengeStatus;
+
+    fn participant(id: u8, score: u64, joined_at: i64, last_submission: i64) -> Participant {
+        Participant {
+            player: Pubkey::new_from_array([id; 32]),
+            challenge: Pubkey::default(),
+            score,
+            has_joined: true,
+            has_submitted: true,
+            joined_at,
+            last_submission,
+            verification_hash: [0; 32],
+            last_attestation_nonce: 0,
+            deposit: 0,
+            waitlisted: false,
+            refunded: false,
+            place: 0,
+            payout: 0,
+            has_claimed: false,
+            bump: 0,
+        }
+    }
+
+    fn places(awards: &[Award]) -> Vec<(u8, u8, u64)> {
+        awards.iter().map(|a| (a.player.to_bytes()[0], a.place, a.amount)).collect()
+    }
+
+    #[test]
+    fn prizes_fold_unfilled_places_into_first() {
+        assert_eq!(compute_prizes(1_000, &[6000, 3000, 1000], 5).unwrap(), vec![600, 300, 100]);
+        assert_eq!(compute_prizes(1_000, &[6000, 3000, 1000], 2).unwrap(), vec![700, 300]);
+        assert_eq!(compute_prizes(1_001, &[6000, 3000, 1000], 3).unwrap(), vec![600, 300, 100]);
+    }
+
+    #[test]
+    fn ranking_ignores_account_order() {
+        let a = participant(1, 50, 10, 20);
+        let b = participant(2, 50, 10, 20);
+        let c = participant(3, 70, 30, 40);
+        let forward = [a.clone(), b.clone(), c.clone()];
+        let backward = [c, b, a];
+
+        for policy in [TiePolicy::SplitEqually, TiePolicy::EarliestSubmission] {
+            let ids = |ps: &[Participant]| -> Vec<u8> {
+                rank_participants(ps, policy)
+                    .iter()
+                    .map(|p| p.player.to_bytes()[0])
+                    .collect()
+            };
+            assert_eq!(ids(&forward), vec![3, 1, 2]);
+            assert_eq!(ids(&backward), vec![3, 1, 2]);
+        }
+    }
+
+    #[test]
+    fn tie_policies_break_or_split_ties() {
+        let ps = [participant(1, 50, 20, 10), participant(2, 50, 10, 20), participant(3, 10, 0, 0)];
+        let prizes = [600, 300, 100];
+        let awards = |policy| {
+            let ranked = rank_participants(&ps, policy);
+            places(&podium_awards(&ranked, &prizes, policy).unwrap())
+        };
+
+        assert_eq!(
+            awards(TiePolicy::SplitEqually),
+            vec![(1, 1, 450), (2, 1, 450), (3, 3, 100)]
+        );
+        assert_eq!(
+            awards(TiePolicy::EarliestSubmission),
+            vec![(1, 1, 600), (2, 2, 300), (3, 3, 100)]
+        );
+        assert_eq!(awards(TiePolicy::EarliestJoin), vec![(2, 1, 600), (1, 2, 300), (3, 3, 100)]);
+    }
+
+    #[test]
+    fn equal_timestamps_still_split() {
+        let ps = [participant(2, 50, 10, 10), participant(1, 50, 10, 10)];
+        let ranked = rank_participants(&ps, TiePolicy::EarliestSubmission);
+        let awards = podium_awards(&ranked, &[700, 300], TiePolicy::EarliestSubmission).unwrap();
+        assert_eq!(places(&awards), vec![(1, 1, 500), (2, 1, 500)]);
+        assert_eq!(winner(&awards), Pubkey::new_from_array([1; 32]));
+    }
+
+    #[test]
+    fn tie_run_past_paid_places_shares_them() {
+        let ps = [
+            participant(1, 90, 0, 0),
+            participant(2, 50, 0, 0),
+            participant(3, 50, 0, 0),
+            participant(4, 50, 0, 0),
+        ];
+        let ranked = rank_participants(&ps, TiePolicy::SplitEqually);
+        let prizes = compute_prizes(1_000, &[6000, 4000], ranked.len()).unwrap();
+        let awards = podium_awards(&ranked, &prizes, TiePolicy::SplitEqually).unwrap();
+
+        // Three players share second place's 400, leaving 1 unit of dust
+        assert_eq!(places(&awards), vec![(1, 1, 600), (2, 2, 133), (3, 2, 133), (4, 2, 133)]);
+        assert_eq!(split_dust(1_000, &awards).unwrap(), (999, 1));
+    }
+
+    #[test]
+    fn podium_without_submissions_returns_stakes() {
+        let mut challenge = challenge(ChallengeStatus::Active);
+        challenge.settlement_mode = SettlementMode::Podium;
+        challenge.payout_bps = vec![6000, 4000];
+        let mut ps = [participant(1, 0, 0, 0), participant(2, 0, 0, 0)];
+        ps.iter_mut().for_each(|p| p.has_submitted = false);
+
+        let awards = compute_awards(&challenge, &ps, 1_901).unwrap();
+        assert_eq!(places(&awards), vec![(1, 0, 950), (2, 0, 950)]);
+        assert_eq!(split_dust(1_901, &awards).unwrap(), (1_900, 1));
+
+        // One submission is enough for the podium to pay out
+        ps[1].has_submitted = true;
+        let awards = compute_awards(&challenge, &ps, 1_901).unwrap();
+        assert_eq!(places(&awards), vec![(2, 1, 1_901)]);
+    }
+
+    #[test]
+    fn dust_covers_every_rounding_remainder() {
+        let ps = [participant(1, 7, 0, 0), participant(2, 7, 0, 0), participant(3, 7, 0, 0)];
+        let ranked = rank_participants(&ps, TiePolicy::SplitEqually);
+        let prizes = compute_prizes(1_003, &[5000, 3000, 2000], ranked.len()).unwrap();
+        let awards = podium_awards(&ranked, &prizes, TiePolicy::SplitEqually).unwrap();
+
+        let (payout, dust) = split_dust(1_003, &awards).unwrap();
+        assert_eq!(payout + dust, 1_003);
+        assert!(awards.iter().all(|a| a.place == 1 && a.amount == 333));
+        assert_eq!(dust, 4);
+    }
+}
@@ -0,0 +1,463 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FitError;
+use crate::settlement::bps_of;
+use crate::state::{Participant, Waitlist};
+
+#[account]
+pub struct CreatorProfile {
+    pub creator: Pubkey,           // 32
+    pub challenge_count: u64,      // 8 (seed of the creator's next challenge)
+    pub bump: u8,                  // 1
+}
+
+impl CreatorProfile {
+    pub const SIZE: usize = 32 + 8 + 1;
+}
+
+#[account]
+pub struct Challenge {
+    pub creator: Pubkey,           // 32
+    pub index: u64,                // 8 (creator's challenge counter at creation)
+    pub entry_fee: u64,            // 8
+    pub total_pool: u64,           // 8
+    pub start_time: i64,           // 8
+    pub end_time: i64,             // 8
+    pub join_deadline: i64,        // 8
+    pub submission_deadline: i64,  // 8 (end_time + grace for late-synced scores)
+    pub participant_count: u32,    // 4 (seated players, excludes the waitlist)
+    pub closed_participants: u32,  // 4
+    pub min_participants: u32,     // 4 (below this at start/end -> Cancelled)
+    pub max_participants: u32,     // 4 (at most MAX_PARTICIPANTS)
+    pub has_waitlist: bool,        // 1 (Waitlist PDA created, closed with the challenge)
+    pub waitlist_count: u32,       // 4 (waitlisted players never promoted)
+    pub waitlist_pool: u64,        // 8 (deposits held for waitlisted players)
+    pub leave_penalty_bps: Option<u16>, // 1 + 2 (None = no leaving after start)
+    pub forfeited: u64,            // 8 (leave penalties kept in total_pool, shared on refund)
+    pub rent_reserve: u64,         // 8 (creator-funded SOL vault rent, outside total_pool)
+    pub status: ChallengeStatus,   // 1
+    pub challenge_type: ChallengeType, // 1
+    pub goal: u64,                 // 8
+    pub payment_mint: Option<Pubkey>, // 1 + 32 (None = SOL)
+    pub is_public: bool,           // 1
+    pub allowlist_root: Option<[u8; 32]>, // 1 + 32 (private challenges only)
+    pub winner: Pubkey,            // 32 (first place)
+    pub settlement_mode: SettlementMode, // 1
+    pub tie_policy: TiePolicy,     // 1 (how equal scores are ranked in podium mode)
+    pub payout_bps: Vec<u16>,      // 4 + 2 * MAX_PAYOUT_PLACES
+    pub platform_fee: u64,         // 8 (unclaimed fee owed to the treasury)
+    pub cranker: Pubkey,           // 32 (non-creator who finalized, if any)
+    pub crank_reward: u64,         // 8 (unclaimed reward owed to the cranker)
+    pub settlement_deadline: i64,  // 8 (unsettled after this -> Expired)
+    pub fee_bps: u16,              // 2 (config terms snapshotted at creation)
+    pub crank_reward_bps: u16,     // 2
+    pub settle_grace_seconds: i64, // 8
+    pub vault_bump: u8,            // 1 (SOL vault PDA)
+    pub escrow_bump: u8,           // 1 (escrow token account PDA, token challenges only)
+    pub bump: u8,                  // 1
+}
+
+impl Challenge {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 4 + 4 + 1 + 4 + 8 + 3 + 8
+        + 8 + 1 + 1 + 8 + 33 + 1 + 33 + 32 + 1 + 1 + (4 + 2 * MAX_PAYOUT_PLACES) + 8 + 32 + 8
+        + 8 + 2 + 2 + 8 + 1 + 1 + 1;
+
+    /// Every seat under `max_participants` is taken
+    pub fn is_full(&self) -> bool {
+        self.participant_count >= self.max_participants
+    }
+
+    /// Credit a join deposit to the prize pool, or hold it aside for a waitlisted player
+    pub fn credit_deposit(&mut self, amount: u64, waitlisted: bool) -> Result<()> {
+        let (pool, count) = if waitlisted {
+            (&mut self.waitlist_pool, &mut self.waitlist_count)
+        } else {
+            (&mut self.total_pool, &mut self.participant_count)
+        };
+        *pool = pool.checked_add(amount).ok_or(FitError::MathOverflow)?;
+        *count = count.checked_add(1).ok_or(FitError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Take a participant out of an open challenge, returning `(refund, penalty)`.
+    /// Waitlisted players and anyone leaving before the start get their full deposit back;
+    /// after the start the creator's leave penalty is kept in the prize pool. The last
+    /// seated player out has nobody left to leave it to, so they pay no penalty and take
+    /// back what earlier leavers forfeited.
+    pub fn withdraw(
+        &mut self,
+        participant: &Participant,
+        waitlist: Option<&mut Waitlist>,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        self.advance_status(now);
+        require!(self.is_open(), FitError::ChallengeClosed);
+        require!(participant.has_joined && !participant.refunded, FitError::NotJoined);
+
+        let deposit = participant.deposit;
+        if participant.waitlisted {
+            // The queue only matters while promotion is still possible
+            if self.status == ChallengeStatus::Pending {
+                let waitlist = waitlist.ok_or(FitError::MissingWaitlist)?;
+                waitlist.players.retain(|player| *player != participant.player);
+            }
+            self.debit_pool(deposit, true)?;
+            self.waitlist_count = self.waitlist_count.checked_sub(1).ok_or(FitError::MathOverflow)?;
+            return Ok((deposit, 0));
+        }
+
+        let bps = if self.status == ChallengeStatus::Pending {
+            0
+        } else {
+            require!(now < self.end_time, FitError::ChallengeEnded);
+            self.leave_penalty_bps.ok_or(FitError::LeaveNotAllowed)?
+        };
+        self.participant_count =
+            self.participant_count.checked_sub(1).ok_or(FitError::MathOverflow)?;
+
+        let (refund, penalty) = if self.participant_count == 0 {
+            let refund = deposit.checked_add(self.forfeited).ok_or(FitError::MathOverflow)?;
+            self.forfeited = 0;
+            (refund, 0)
+        } else {
+            let penalty = bps_of(deposit, bps)?;
+            self.forfeited = self.forfeited.checked_add(penalty).ok_or(FitError::MathOverflow)?;
+            (deposit.checked_sub(penalty).ok_or(FitError::MathOverflow)?, penalty)
+        };
+        self.debit_pool(refund, false)?;
+        Ok((refund, penalty))
+    }
+
+    /// Release a participant's refund from a cancelled or expired challenge and return it.
+    /// Seated players get their deposit plus a share of the leave penalties pro rata to
+    /// their deposit; the last claimant's share is whatever remains, so none is left over.
+    pub fn take_refund(&mut self, participant: &Participant) -> Result<u64> {
+        let deposit = participant.deposit;
+        if participant.waitlisted {
+            self.debit_pool(deposit, true)?;
+            return Ok(deposit);
+        }
+
+        // Deposits of the players still to be refunded
+        let stakes = self.total_pool.checked_sub(self.forfeited).ok_or(FitError::MathOverflow)?;
+        let share = if self.forfeited == 0 {
+            0
+        } else if stakes == 0 {
+            // Only zero deposits left (zero entry fee or a fee-eaten deposit): no weights
+            // to split by, so the first of them takes the penalties
+            self.forfeited
+        } else {
+            (self.forfeited as u128)
+                .checked_mul(deposit as u128)
+                .map(|weighted| weighted / stakes as u128)
+                .and_then(|share| u64::try_from(share).ok())
+                .ok_or(FitError::MathOverflow)?
+        };
+        let refund = deposit.checked_add(share).ok_or(FitError::MathOverflow)?;
+        self.forfeited = self.forfeited.checked_sub(share).ok_or(FitError::MathOverflow)?;
+        self.debit_pool(refund, false)?;
+        Ok(refund)
+    }
+
+    /// What the treasury can withdraw: the platform fee once settled. Cancelled and
+    /// expired challenges owe it nothing; leave penalties go to the remaining players.
+    pub fn treasury_due(&self) -> Result<u64> {
+        match self.status {
+            ChallengeStatus::Ended => Ok(self.platform_fee),
+            ChallengeStatus::Cancelled | ChallengeStatus::Expired => Ok(0),
+            _ => err!(FitError::ChallengeNotFinalized),
+        }
+    }
+
+    /// Release a payout or refunded deposit from whichever pool holds it
+    pub fn debit_pool(&mut self, amount: u64, waitlisted: bool) -> Result<()> {
+        let pool = if waitlisted { &mut self.waitlist_pool } else { &mut self.total_pool };
+        *pool = pool.checked_sub(amount).ok_or(FitError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Everything the escrow is meant to hold: the prize pool plus queued deposits
+    pub fn escrowed(&self) -> Result<u64> {
+        Ok(self.total_pool.checked_add(self.waitlist_pool).ok_or(FitError::MathOverflow)?)
+    }
+
+    /// Seat the player at the head of the waitlist after someone leaves before the start.
+    /// Their deposit moves from the waitlist pool into the prize pool.
+    pub fn promote_from_waitlist(
+        &mut self,
+        waitlist: &mut Waitlist,
+        next: &mut Participant,
+    ) -> Result<()> {
+        require!(self.status == ChallengeStatus::Pending, FitError::ChallengeClosed);
+        require!(!self.is_full(), FitError::ChallengeFull);
+        require!(next.waitlisted && !next.refunded, FitError::InvalidParticipant);
+        require!(
+            waitlist.players.first() == Some(&next.player),
+            FitError::InvalidParticipant
+        );
+
+        waitlist.players.remove(0);
+        self.debit_pool(next.deposit, true)?;
+        self.waitlist_count = self.waitlist_count.checked_sub(1).ok_or(FitError::MathOverflow)?;
+        self.credit_deposit(next.deposit, false)?;
+        next.waitlisted = false;
+        Ok(())
+    }
+
+    /// Scheduled or running, i.e. not yet settled, cancelled or expired
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, ChallengeStatus::Pending | ChallengeStatus::Active)
+    }
+
+    /// Apply the clock to the schedule: a `Pending` challenge becomes `Active` at its
+    /// start time, unless it is short of `min_participants`. Challenges that started
+    /// immediately are held to the minimum at `end_time` instead.
+    /// Returns true if the challenge was cancelled for lack of participants.
+    pub fn advance_status(&mut self, now: i64) -> bool {
+        let checkpoint = match self.status {
+            ChallengeStatus::Pending => self.start_time,
+            ChallengeStatus::Active => self.end_time,
+            _ => return false,
+        };
+        if now < checkpoint {
+            return false;
+        }
+        if self.participant_count < self.min_participants {
+            self.status = ChallengeStatus::Cancelled;
+            return true;
+        }
+        self.status = ChallengeStatus::Active;
+        false
+    }
+
+    /// Refunds are open after cancellation or a missed settlement deadline
+    pub fn is_refundable(&self) -> bool {
+        matches!(self.status, ChallengeStatus::Cancelled | ChallengeStatus::Expired)
+    }
+
+    /// Move an unsettled challenge to `Expired` once its settlement deadline passes.
+    /// Returns true if the status changed.
+    pub fn expire_if_overdue(&mut self, now: i64) -> bool {
+        if self.is_open() && now >= self.settlement_deadline {
+            self.status = ChallengeStatus::Expired;
+            return true;
+        }
+        false
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeStatus {
+    Pending,    // Created with a future start time; joins open, scores not yet accepted
+    Active,
+    Ended,
+    Cancelled,
+    Expired,    // Not settled before the settlement deadline; stakes are refundable
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementMode {
+    Podium,           // Top places split the pool per `payout_bps`
+    GoalSplitEqual,   // Everyone with score >= goal shares the pool equally
+    GoalSplitProRata, // Everyone with score >= goal shares the pool pro-rata to score
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TiePolicy {
+    SplitEqually,       // Tied players share the prizes of the places they occupy
+    EarliestSubmission, // Earlier `last_submission` takes the higher place
+    EarliestJoin,       // Earlier `joined_at` takes the higher place
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeType {
+    Steps,      // Daily step count
+    Distance,   // Walking/running distance (meters)
+    Duration,   // Active minutes
+    Calories,   // Calories burned
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// An open SOL challenge running from 100 to 200 with a 10% leave penalty
+    pub(crate) fn challenge(status: ChallengeStatus) -> Challenge {
+        Challenge {
+            creator: Pubkey::default(),
+            index: 0,
+            entry_fee: 100,
+            total_pool: 0,
+            start_time: 100,
+            end_time: 200,
+            join_deadline: 200,
+            submission_deadline: 200,
+            participant_count: 0,
+            closed_participants: 0,
+            min_participants: 2,
+            max_participants: MAX_PARTICIPANTS,
+            has_waitlist: false,
+            waitlist_count: 0,
+            waitlist_pool: 0,
+            leave_penalty_bps: Some(1_000),
+            forfeited: 0,
+            rent_reserve: 0,
+            status,
+            challenge_type: ChallengeType::Steps,
+            goal: 1,
+            payment_mint: None,
+            is_public: true,
+            allowlist_root: None,
+            winner: Pubkey::default(),
+            settlement_mode: SettlementMode::Podium,
+            tie_policy: TiePolicy::SplitEqually,
+            payout_bps: vec![BPS_DENOMINATOR as u16],
+            platform_fee: 0,
+            cranker: Pubkey::default(),
+            crank_reward: 0,
+            settlement_deadline: 300,
+            fee_bps: 0,
+            crank_reward_bps: 0,
+            settle_grace_seconds: 0,
+            vault_bump: 0,
+            escrow_bump: 0,
+            bump: 0,
+        }
+    }
+
+    fn player(id: u8, deposit: u64, waitlisted: bool) -> Participant {
+        Participant {
+            player: Pubkey::new_from_array([id; 32]),
+            challenge: Pubkey::default(),
+            score: 0,
+            has_joined: true,
+            has_submitted: false,
+            joined_at: 0,
+            last_submission: 0,
+            verification_hash: [0; 32],
+            last_attestation_nonce: 0,
+            deposit,
+            waitlisted,
+            refunded: false,
+            place: 0,
+            payout: 0,
+            has_claimed: false,
+            bump: 0,
+        }
+    }
+
+    /// `challenge` with `players` credited as joined
+    fn joined(status: ChallengeStatus, players: &[&Participant]) -> Challenge {
+        let mut challenge = challenge(status);
+        for p in players {
+            challenge.credit_deposit(p.deposit, p.waitlisted).unwrap();
+        }
+        challenge
+    }
+
+    #[test]
+    fn size_covers_serialized_challenge() {
+        let mut challenge = challenge(ChallengeStatus::Active);
+        challenge.payment_mint = Some(Pubkey::default());
+        challenge.allowlist_root = Some([0; 32]);
+        challenge.payout_bps = vec![0; MAX_PAYOUT_PLACES];
+        assert_eq!(challenge.try_to_vec().unwrap().len(), Challenge::SIZE);
+    }
+
+    #[test]
+    fn leave_before_start_refunds_in_full() {
+        let (a, b) = (player(1, 100, false), player(2, 100, false));
+        let mut challenge = joined(ChallengeStatus::Pending, &[&a, &b]);
+
+        assert_eq!(challenge.withdraw(&a, None, 50).unwrap(), (100, 0));
+        assert_eq!((challenge.participant_count, challenge.total_pool), (1, 100));
+        assert_eq!(challenge.forfeited, 0);
+    }
+
+    #[test]
+    fn leave_after_start_keeps_penalty_in_pool() {
+        let (a, b, c) = (player(1, 100, false), player(2, 100, false), player(3, 100, false));
+        let mut challenge = joined(ChallengeStatus::Active, &[&a, &b, &c]);
+
+        assert_eq!(challenge.withdraw(&a, None, 150).unwrap(), (90, 10));
+        assert_eq!((challenge.participant_count, challenge.total_pool), (2, 210));
+        assert_eq!(challenge.forfeited, 10);
+
+        challenge.leave_penalty_bps = None;
+        assert_eq!(
+            challenge.withdraw(&b, None, 150).unwrap_err(),
+            FitError::LeaveNotAllowed.into()
+        );
+    }
+
+    #[test]
+    fn last_seated_leaver_reclaims_forfeited() {
+        let (a, b) = (player(1, 100, false), player(2, 100, false));
+        let mut challenge = joined(ChallengeStatus::Active, &[&a, &b]);
+
+        assert_eq!(challenge.withdraw(&a, None, 150).unwrap(), (90, 10));
+        assert_eq!(challenge.withdraw(&b, None, 150).unwrap(), (110, 0));
+        assert_eq!((challenge.total_pool, challenge.forfeited), (0, 0));
+    }
+
+    #[test]
+    fn cancelled_refunds_share_penalties_by_deposit() {
+        let leaver = player(1, 100, false);
+        let (a, b, c) = (player(2, 100, false), player(3, 100, false), player(4, 50, false));
+        let mut challenge = joined(ChallengeStatus::Active, &[&leaver, &a, &b, &c]);
+        challenge.withdraw(&leaver, None, 150).unwrap();
+        challenge.status = ChallengeStatus::Cancelled;
+        assert_eq!((challenge.total_pool, challenge.forfeited), (260, 10));
+
+        // 10 * 100/250 = 4, then 6 * 100/150 = 4, and the last claimant takes the rest
+        assert_eq!(challenge.take_refund(&a).unwrap(), 104);
+        assert_eq!(challenge.take_refund(&b).unwrap(), 104);
+        assert_eq!(challenge.take_refund(&c).unwrap(), 52);
+        assert_eq!((challenge.total_pool, challenge.forfeited), (0, 0));
+    }
+
+    #[test]
+    fn waitlisted_refund_comes_from_waitlist_pool() {
+        let (a, b, queued) = (player(1, 100, false), player(2, 100, false), player(3, 100, true));
+        let mut challenge = joined(ChallengeStatus::Cancelled, &[&a, &b, &queued]);
+        challenge.forfeited = 10;
+        challenge.total_pool += 10;
+
+        assert_eq!(challenge.take_refund(&queued).unwrap(), 100);
+        assert_eq!((challenge.waitlist_pool, challenge.total_pool), (0, 210));
+        assert_eq!(challenge.forfeited, 10);
+    }
+
+    #[test]
+    fn zero_stake_refunds_do_not_fail() {
+        let (a, b) = (player(1, 0, false), player(2, 0, false));
+        let mut challenge = joined(ChallengeStatus::Cancelled, &[&a, &b]);
+        assert_eq!(challenge.take_refund(&a).unwrap(), 0);
+
+        // Penalties with only zero deposits left go to the next claimant
+        challenge.forfeited = 5;
+        challenge.total_pool = 5;
+        assert_eq!(challenge.take_refund(&b).unwrap(), 5);
+        assert_eq!((challenge.total_pool, challenge.forfeited), (0, 0));
+    }
+
+    #[test]
+    fn promotion_moves_deposit_into_prize_pool() {
+        let (a, b) = (player(1, 100, false), player(2, 100, false));
+        let mut next = player(3, 90, true);
+        let mut challenge = joined(ChallengeStatus::Pending, &[&a, &b, &next]);
+        challenge.max_participants = 2;
+        let mut waitlist = Waitlist {
+            challenge: Pubkey::default(),
+            players: vec![next.player],
+            bump: 0,
+        };
+
+        challenge.withdraw(&a, Some(&mut waitlist), 50).unwrap();
+        challenge.promote_from_waitlist(&mut waitlist, &mut next).unwrap();
+        assert!(!next.waitlisted && waitlist.players.is_empty());
+        assert_eq!((challenge.participant_count, challenge.total_pool), (2, 190));
+        assert_eq!((challenge.waitlist_count, challenge.waitlist_pool), (0, 0));
+    }
+}
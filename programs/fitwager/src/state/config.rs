@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FitError;
+
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,             // 32
+    pub treasury: Pubkey,          // 32
+    pub fee_bps: u16,              // 2
+    pub accepted_mints: Vec<AcceptedMint>, // 4 + AcceptedMint::SIZE * MAX_ACCEPTED_MINTS
+    pub min_entry_fee_sol: u64,    // 8 (lamports)
+    pub settle_grace_seconds: i64, // 8 (after submission_deadline, before anyone may finalize)
+    pub crank_reward_bps: u16,     // 2 (share of the platform fee paid to the crank)
+    pub settlement_window_seconds: i64, // 8 (after submission_deadline, until unsettled expire)
+    pub oracles: Vec<Pubkey>,      // 4 + 32 * MAX_ORACLES
+    pub paused: bool,              // 1 (blocks create, join, submit and settlement)
+    pub bump: u8,                  // 1
+}
+
+impl ProgramConfig {
+    pub const SIZE: usize = 32 + 32 + 2 + (4 + AcceptedMint::SIZE * MAX_ACCEPTED_MINTS) + 8
+        + 8 + 2 + 8 + (4 + 32 * MAX_ORACLES) + 1 + 1;
+
+    /// Minimum entry fee in the asset's base units (lamports for SOL)
+    pub fn min_entry_fee(&self, payment_mint: Option<Pubkey>) -> Result<u64> {
+        match payment_mint {
+            Some(mint) => self
+                .accepted_mints
+                .iter()
+                .find(|m| m.mint == mint)
+                .map(|m| m.min_entry_fee)
+                .ok_or_else(|| error!(FitError::MintNotAccepted)),
+            None => Ok(self.min_entry_fee_sol),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AcceptedMint {
+    pub mint: Pubkey,              // 32
+    pub min_entry_fee: u64,        // 8 (base units of this mint)
+}
+
+impl AcceptedMint {
+    pub const SIZE: usize = 32 + 8;
+}
@@ -0,0 +1,9 @@
+pub mod challenge;
+pub mod config;
+pub mod participant;
+pub mod waitlist;
+
+pub use challenge::*;
+pub use config::*;
+pub use participant::*;
+pub use waitlist::*;
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Participant {
+    pub player: Pubkey,            // 32
+    pub challenge: Pubkey,         // 32
+    pub score: u64,                // 8
+    pub has_joined: bool,          // 1
+    pub has_submitted: bool,       // 1
+    pub joined_at: i64,            // 8
+    pub last_submission: i64,      // 8
+    pub verification_hash: [u8; 32], // 32
+    pub last_attestation_nonce: u64, // 8 (replay guard: nonce of the last accepted attestation)
+    pub deposit: u64,              // 8 (amount actually escrowed on join)
+    pub waitlisted: bool,          // 1 (queued for a seat, not yet playing)
+    pub refunded: bool,            // 1
+    pub place: u8,                 // 1 (0 = no prize)
+    pub payout: u64,               // 8 (entitlement recorded at finalize)
+    pub has_claimed: bool,         // 1
+    pub bump: u8,                  // 1
+}
+
+impl Participant {
+    pub const SIZE: usize = 32 + 32 + 8 + 1 + 1 + 8 + 8 + 32 + 8 + 8 + 1 + 1 + 1 + 8 + 1 + 1;
+}
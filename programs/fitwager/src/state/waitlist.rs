@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_WAITLIST;
+use crate::errors::FitError;
+use crate::state::{Challenge, ChallengeStatus};
+
+/// FIFO queue of players waiting for a seat on a capped challenge
+#[account]
+pub struct Waitlist {
+    pub challenge: Pubkey,         // 32
+    pub players: Vec<Pubkey>,      // 4 + 32 * MAX_WAITLIST
+    pub bump: u8,                  // 1
+}
+
+impl Waitlist {
+    pub const SIZE: usize = 32 + (4 + 32 * MAX_WAITLIST) + 1;
+
+    /// Queue a player. Only possible before start, while a seat can still free up.
+    pub fn enqueue(&mut self, challenge: &Challenge, player: Pubkey) -> Result<()> {
+        require!(challenge.status == ChallengeStatus::Pending, FitError::ChallengeFull);
+        require!(self.players.len() < MAX_WAITLIST, FitError::WaitlistFull);
+        self.players.push(player);
+        Ok(())
+    }
+}